@@ -0,0 +1,138 @@
+/// Intrinsic calibration parameters for one of the project's cameras.
+///
+/// A project can define several of these -- one per physical camera -- each identified by name
+/// and referenced by the `MountCalibration`s that orient it in individual scan positions. These
+/// implement RiSCAN's pinhole-plus-distortion model, which maps a point in camera coordinates
+/// (CAMCS) to a sub-pixel coordinate in the camera's image plane.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CameraCalibration {
+    name: String,
+    width: u32,
+    height: u32,
+    fx: f64,
+    fy: f64,
+    cx: f64,
+    cy: f64,
+    k1: f64,
+    k2: f64,
+    k3: f64,
+    p1: f64,
+    p2: f64,
+}
+
+impl CameraCalibration {
+    /// Creates a new camera calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::new("calib0", 1920, 1080, 1350., 1350., 960.,
+    ///                                                  540., 0., 0., 0., 0., 0.);
+    /// ```
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    pub fn new(name: &str,
+               width: u32,
+               height: u32,
+               fx: f64,
+               fy: f64,
+               cx: f64,
+               cy: f64,
+               k1: f64,
+               k2: f64,
+               k3: f64,
+               p1: f64,
+               p2: f64)
+               -> CameraCalibration {
+        CameraCalibration {
+            name: name.to_string(),
+            width: width,
+            height: height,
+            fx: fx,
+            fy: fy,
+            cx: cx,
+            cy: cy,
+            k1: k1,
+            k2: k2,
+            k3: k3,
+            p1: p1,
+            p2: p2,
+        }
+    }
+
+    /// Returns this camera calibration's name, as used to reference it from a mount calibration.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns this camera's image width, in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns this camera's image height, in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Projects a point in camera coordinates (CAMCS) into this camera's image plane.
+    ///
+    /// Returns `None` if the point is behind the camera (`z <= 0`) or if the distorted pixel
+    /// coordinate falls outside of `[0, width) x [0, height)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::CameraCalibration;
+    /// let camera_calibration = CameraCalibration::new("calib0", 1920, 1080, 1350., 1350., 960.,
+    ///                                                  540., 0., 0., 0., 0., 0.);
+    /// let uv = camera_calibration.project((0., 0., 1.));
+    /// ```
+    pub fn project(&self, (x, y, z): (f64, f64, f64)) -> Option<(f64, f64)> {
+        if z <= 0. {
+            return None;
+        }
+        let x = x / z;
+        let y = y / z;
+        let r2 = x * x + y * y;
+        let radial = 1. + self.k1 * r2 + self.k2 * r2 * r2 + self.k3 * r2 * r2 * r2;
+        let xd = x * radial + 2. * self.p1 * x * y + self.p2 * (r2 + 2. * x * x);
+        let yd = y * radial + self.p1 * (r2 + 2. * y * y) + 2. * self.p2 * x * y;
+        let u = self.fx * xd + self.cx;
+        let v = self.fy * yd + self.cy;
+        if u < 0. || u >= self.width as f64 || v < 0. || v >= self.height as f64 {
+            None
+        } else {
+            Some((u, v))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera_calibration() -> CameraCalibration {
+        CameraCalibration::new("calib0", 100, 100, 50., 50., 50., 50., 0., 0., 0., 0., 0.)
+    }
+
+    #[test]
+    fn project_behind_camera() {
+        let camera_calibration = camera_calibration();
+        assert!(camera_calibration.project((0., 0., -1.)).is_none());
+        assert!(camera_calibration.project((0., 0., 0.)).is_none());
+    }
+
+    #[test]
+    fn project_center() {
+        let camera_calibration = camera_calibration();
+        assert_eq!(Some((50., 50.)), camera_calibration.project((0., 0., 1.)));
+    }
+
+    #[test]
+    fn project_outside_bounds() {
+        let camera_calibration = camera_calibration();
+        assert!(camera_calibration.project((10., 10., 1.)).is_none());
+    }
+}
@@ -0,0 +1,93 @@
+use {CameraCalibration, MountCalibration};
+use std::path::{Path, PathBuf};
+
+/// One of the photos taken from a scan position, with the calibration needed to project points
+/// into it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Image {
+    path: PathBuf,
+    camera_calibration: CameraCalibration,
+    mount_calibration: MountCalibration,
+}
+
+impl Image {
+    /// Creates a new image from its file path, camera calibration, and mount calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::{CameraCalibration, Image, Matrix, MountCalibration};
+    /// # use nalgebra::Eye;
+    /// let camera_calibration = CameraCalibration::new("calib0", 1920, 1080, 1350., 1350., 960.,
+    ///                                                  540., 0., 0., 0., 0., 0.);
+    /// let mount_calibration = MountCalibration::new("mount0", "calib0", Matrix::new_identity(4));
+    /// let image = Image::new("IMAGES/image001.jpg", camera_calibration, mount_calibration);
+    /// ```
+    pub fn new<P: AsRef<Path>>(path: P,
+                                camera_calibration: CameraCalibration,
+                                mount_calibration: MountCalibration)
+                                -> Image {
+        Image {
+            path: path.as_ref().to_path_buf(),
+            camera_calibration: camera_calibration,
+            mount_calibration: mount_calibration,
+        }
+    }
+
+    /// Returns the path to this image's file, relative to the project directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns this image's camera calibration.
+    pub fn camera_calibration(&self) -> &CameraCalibration {
+        &self.camera_calibration
+    }
+
+    /// Returns this image's mount calibration.
+    pub fn mount_calibration(&self) -> &MountCalibration {
+        &self.mount_calibration
+    }
+}
+
+/// A decoded raster of RGB pixels backing an `Image`.
+///
+/// This is a minimal buffer so that `ScanPosition::color_of` can sample nearest-neighbor color
+/// without this crate needing to depend on an image-decoding library.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ImageBuffer {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl ImageBuffer {
+    /// Creates a new image buffer from packed, row-major RGB triplets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != width * height * 3`.
+    pub fn new(width: u32, height: u32, data: Vec<u8>) -> ImageBuffer {
+        assert_eq!((width * height * 3) as usize, data.len());
+        ImageBuffer {
+            width: width,
+            height: height,
+            data: data,
+        }
+    }
+
+    /// Returns the nearest-neighbor RGB color for the given sub-pixel coordinate.
+    ///
+    /// Returns `None` if the (rounded) coordinate falls outside of the buffer.
+    pub fn nearest(&self, u: f64, v: f64) -> Option<(u8, u8, u8)> {
+        let u = u.round();
+        let v = v.round();
+        if u < 0. || v < 0. || u >= self.width as f64 || v >= self.height as f64 {
+            return None;
+        }
+        let index = (v as u32 * self.width + u as u32) as usize * 3;
+        Some((self.data[index], self.data[index + 1], self.data[index + 2]))
+    }
+}
@@ -32,18 +32,34 @@ extern crate alga;
 #[macro_use]
 extern crate approx;
 extern crate nalgebra;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "serde")]
+extern crate serde_json;
 extern crate sxd_document;
 extern crate sxd_xpath;
 
 #[macro_use]
 mod macros;
 
+mod camera;
 mod image;
+mod mount_calibration;
 mod project;
+mod rsp;
+mod scan;
+#[cfg(feature = "serde")]
+mod serializable_matrix;
 mod utils;
 
-pub use image::Image;
+pub use camera::CameraCalibration;
+pub use image::{Image, ImageBuffer};
+pub use mount_calibration::MountCalibration;
 pub use project::{Project, rsp_path};
+pub use scan::{InputCloudFormat, Scan};
 
 /// Our custom error enum.
 #[derive(Debug)]
@@ -52,6 +68,8 @@ pub enum Error {
     Io(std::io::Error),
     /// Wrapper around `std::num::ParseFloatError`.
     ParseFloat(std::num::ParseFloatError),
+    /// Wrapper around `std::num::ParseIntError`.
+    ParseInt(std::num::ParseIntError),
     /// Invalid project path.
     ProjectPath(std::path::PathBuf),
     /// An error that occurs while parsing an xml file.
@@ -60,6 +78,19 @@ pub enum Error {
     Xpath(sxd_xpath::Error),
     /// The provided xpath was not found.
     XpathNotFound(String),
+    /// The expected scan data file is missing from disk.
+    MissingScanData(std::path::PathBuf),
+    /// The composed POP/SOP matrix isn't invertible.
+    MatrixNotInvertible,
+    /// A 4x4 matrix string didn't contain exactly sixteen valid floats.
+    MatrixParse(String),
+    /// The provided path isn't inside of a RiSCAN Pro project.
+    NotAProject(std::path::PathBuf),
+    /// The provided path isn't a scan position directory inside of a RiSCAN Pro project.
+    NotAScanPosition(std::path::PathBuf),
+    /// Wrapper around `serde_json::Error`.
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
 }
 
 /// Our custom result type.
@@ -68,9 +99,18 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// Our type of Transform3.
 pub type Transform3 = nalgebra::Transform3<f64>;
 
+/// Our type of Projective3.
+pub type Projective3 = nalgebra::Projective3<f64>;
+
 /// Our type of Point3.
 pub type Point3 = nalgebra::Point3<f64>;
 
+/// Our type of 4x4 matrix, used for POP, SOP, and mount calibrations.
+pub type Matrix = nalgebra::Matrix4<f64>;
+
+/// Our type of homogeneous 4-vector.
+pub type Vector = nalgebra::Vector4<f64>;
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
         Error::Io(err)
@@ -83,6 +123,12 @@ impl From<std::num::ParseFloatError> for Error {
     }
 }
 
+impl From<std::num::ParseIntError> for Error {
+    fn from(err: std::num::ParseIntError) -> Error {
+        Error::ParseInt(err)
+    }
+}
+
 impl From<(usize, Vec<sxd_document::parser::Error>)> for Error {
     fn from((n, v): (usize, Vec<sxd_document::parser::Error>)) -> Error {
         Error::XmlParse(n, v)
@@ -94,3 +140,10 @@ impl From<sxd_xpath::Error> for Error {
         Error::Xpath(err)
     }
 }
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
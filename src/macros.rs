@@ -0,0 +1,9 @@
+/// Unwraps an `Option`, returning early with the given error expression if it is `None`.
+macro_rules! try_opt {
+    ($option:expr, $error:expr) => {
+        match $option {
+            Some(value) => value,
+            None => return Err($error.into()),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use Matrix;
+
+/// A camera mount calibration.
+///
+/// This is the fixed transform that orients a mounted camera relative to the scanner's own
+/// coordinate system (SOCS), mapping SOCS points into that camera's coordinate system (CAMCS).
+/// Mount calibrations are scoped to a scan position, since the same camera can be remounted
+/// (and thus re-calibrated) between setups.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MountCalibration {
+    name: String,
+    camera_calibration_name: String,
+    #[cfg_attr(feature = "serde", serde(with = "::serializable_matrix::matrix"))]
+    matrix: Matrix,
+}
+
+impl MountCalibration {
+    /// Creates a new mount calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::{Matrix, MountCalibration};
+    /// # use nalgebra::Eye;
+    /// let mount_calibration = MountCalibration::new("mount0", "calib0", Matrix::new_identity(4));
+    /// ```
+    pub fn new(name: &str, camera_calibration_name: &str, matrix: Matrix) -> MountCalibration {
+        MountCalibration {
+            name: name.to_string(),
+            camera_calibration_name: camera_calibration_name.to_string(),
+            matrix: matrix,
+        }
+    }
+
+    /// Returns this mount calibration's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the camera calibration that this mount calibration orients.
+    pub fn camera_calibration_name(&self) -> &str {
+        &self.camera_calibration_name
+    }
+
+    /// Returns this mount calibration's SOCS-to-CAMCS matrix.
+    pub fn matrix(&self) -> Matrix {
+        self.matrix
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use nalgebra::Eye;
+
+    #[test]
+    fn matrix_serializes_as_a_flat_array_of_sixteen_floats() {
+        #[cfg_attr(rustfmt, rustfmt_skip)]
+        let matrix = Matrix::new(1., 0., 0., 1.,
+                                  0., 1., 0., 2.,
+                                  0., 0., 1., 3.,
+                                  0., 0., 0., 1.);
+        let mount_calibration = MountCalibration::new("mount0", "calib0", matrix);
+        let json = ::serde_json::to_value(&mount_calibration).unwrap();
+        let values = json.get("matrix").unwrap().as_array().unwrap();
+        let expected = [1., 0., 0., 1., 0., 1., 0., 2., 0., 0., 1., 3., 0., 0., 0., 1.];
+        assert_eq!(16, values.len());
+        for (expected, actual) in expected.iter().zip(values.iter()) {
+            assert_eq!(*expected, actual.as_f64().unwrap());
+        }
+    }
+
+    #[test]
+    fn matrix_round_trips_through_json() {
+        let matrix = Matrix::new_identity(4);
+        let mount_calibration = MountCalibration::new("mount0", "calib0", matrix);
+        let json = ::serde_json::to_string(&mount_calibration).unwrap();
+        let roundtrip: MountCalibration = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(mount_calibration, roundtrip);
+    }
+}
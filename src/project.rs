@@ -1,17 +1,51 @@
-use {Camera, Projective3, Result};
+use {CameraCalibration, Error, Image, MountCalibration, Projective3, Result, Scan, ScanPosition};
 use rsp::Rsp;
-use std::path::Path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use utils::parse_matrix4;
 
-/// A RiSCAN Pro project.
+/// Resolves the path to a project's `.rsp` file.
+///
+/// If `path` is a directory, this returns the first `*.rsp` file found inside of it; whether the
+/// directory holds more than one is not checked. If `path` is already a file, it's returned
+/// unchanged.
 ///
-/// This project isn't a one-to-one mapping to Riegl's XML structure. We've chosen to cut cornerns
-/// in order to easily support *our* use case. Specifically:
+/// # Examples
+///
+/// ```
+/// use riscan_pro::rsp_path;
+/// let path = rsp_path("data/project.RiSCAN").unwrap();
+/// assert_eq!("data/project.RiSCAN/project.rsp", path.to_string_lossy());
+/// ```
+pub fn rsp_path<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
+    let path = path.as_ref();
+    if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            let entry_path = entry.path();
+            if entry_path.extension().map(|ext| ext == "rsp").unwrap_or(false) {
+                return Ok(entry_path);
+            }
+        }
+        Err(Error::ProjectPath(path.to_path_buf()))
+    } else {
+        Ok(path.to_path_buf())
+    }
+}
+
+/// A RiSCAN Pro project.
 ///
-/// - Only one or zero camera calibrations are supported, not more than one.
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// This project isn't a one-to-one mapping to Riegl's XML structure. We've chosen to cut corners
+/// in order to easily support *our* use case.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Project {
-    camera: Option<Camera>,
+    camera_calibrations: HashMap<String, CameraCalibration>,
+    path: PathBuf,
+    #[cfg_attr(feature = "serde", serde(with = "::serializable_matrix::projective3"))]
     pop: Projective3,
+    scan_positions: HashMap<String, ScanPosition>,
 }
 
 impl Project {
@@ -32,6 +66,19 @@ impl Project {
         Project::new(&rsp)
     }
 
+    /// Returns this project's directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let path = project.path();
+    /// ```
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     /// Returns this project's POP.
     ///
     /// # Examples
@@ -45,21 +92,154 @@ impl Project {
         self.pop
     }
 
-    /// Returns this project's camera calibration, if it exists.
+    /// Returns this project's camera calibration with the given name, if it exists.
     ///
     /// # Examples
     ///
     /// ```
     /// use riscan_pro::Project;
     /// let project = Project::from_path("data/project.RiSCAN").unwrap();
-    /// let camera = project.camera().unwrap();
+    /// let camera_calibration = project.camera_calibration("calib0").unwrap();
+    /// ```
+    pub fn camera_calibration(&self, name: &str) -> Option<&CameraCalibration> {
+        self.camera_calibrations.get(name)
+    }
+
+    /// Adds a camera calibration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{CameraCalibration, Project};
+    /// let mut project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let camera_calibration = CameraCalibration::new("calib1", 1920, 1080, 1350., 1350., 960.,
+    ///                                                  540., 0., 0., 0., 0., 0.);
+    /// project.add_camera_calibration(camera_calibration);
     /// ```
-    pub fn camera(&self) -> Option<Camera> {
-        self.camera
+    pub fn add_camera_calibration(&mut self, camera_calibration: CameraCalibration) {
+        self.camera_calibrations.insert(camera_calibration.name().to_string(), camera_calibration);
+    }
+
+    /// Returns a reference to the scan position with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let scan_position = project.scan_position("SP01").unwrap();
+    /// ```
+    pub fn scan_position(&self, name: &str) -> Option<&ScanPosition> {
+        self.scan_positions.get(name)
+    }
+
+    /// Adds a scan position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use riscan_pro::{Project, ScanPosition};
+    /// let mut project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let mut scan_position = ScanPosition::new();
+    /// scan_position.set_name("SP02");
+    /// project.add_scan_position(scan_position);
+    /// ```
+    pub fn add_scan_position(&mut self, scan_position: ScanPosition) {
+        self.scan_positions.insert(scan_position.name().to_string(), scan_position);
+    }
+
+    /// Writes this project's metadata -- POP, every scan position's SOP, camera calibrations,
+    /// and image mount matrices -- to a writer as a single JSON document.
+    ///
+    /// Only available when the `serde` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # fn main() {
+    /// use riscan_pro::Project;
+    /// let project = Project::from_path("data/project.RiSCAN").unwrap();
+    /// let mut json = Vec::new();
+    /// project.to_json_writer(&mut json).unwrap();
+    /// # }
+    /// # #[cfg(not(feature = "serde"))]
+    /// # fn main() {}
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json_writer<W: ::std::io::Write>(&self, writer: W) -> Result<()> {
+        ::serde_json::to_writer(writer, self).map_err(From::from)
     }
 
     fn new(rsp: &Rsp) -> Result<Project> {
-        unimplemented!()
+        let pop = Projective3::from_matrix_unchecked(parse_matrix4(&rsp.text("/project/pose/matrix")?)?);
+
+        let mut camera_calibrations = HashMap::new();
+        for node in rsp.nodes("/project/calibrations/camcalibs/camcalib")? {
+            let camera_calibration = CameraCalibration::new(&rsp.text_on("name", node)?,
+                                                              rsp.text_on("width", node)?.parse()?,
+                                                              rsp.text_on("height", node)?.parse()?,
+                                                              rsp.text_on("fx", node)?.parse()?,
+                                                              rsp.text_on("fy", node)?.parse()?,
+                                                              rsp.text_on("cx", node)?.parse()?,
+                                                              rsp.text_on("cy", node)?.parse()?,
+                                                              rsp.text_on("k1", node)?.parse()?,
+                                                              rsp.text_on("k2", node)?.parse()?,
+                                                              rsp.text_on("k3", node)?.parse()?,
+                                                              rsp.text_on("p1", node)?.parse()?,
+                                                              rsp.text_on("p2", node)?.parse()?);
+            camera_calibrations.insert(camera_calibration.name().to_string(), camera_calibration);
+        }
+
+        let mut scan_positions = HashMap::new();
+        for node in rsp.nodes("/project/scanpositions/scanposition")? {
+            let mut scan_position = ScanPosition::new();
+            scan_position.set_name(&rsp.text_on("name", node)?);
+            scan_position.set_pop(*pop.matrix());
+            scan_position.set_sop(parse_matrix4(&rsp.text_on("sop/matrix", node)?)?);
+
+            let mut mount_calibrations: HashMap<String, MountCalibration> = HashMap::new();
+            for mount_node in rsp.nodes_on("mountcalibrations/mountcalibration", node)? {
+                let mount_calibration = MountCalibration::new(&rsp.text_on("name", mount_node)?,
+                                                               &rsp.text_on("camcalibref",
+                                                                             mount_node)?,
+                                                               parse_matrix4(&rsp.text_on("matrix",
+                                                                                            mount_node)?)?);
+                mount_calibrations.insert(mount_calibration.name().to_string(), mount_calibration);
+            }
+
+            for scan_node in rsp.nodes_on("scans/scan", node)? {
+                let mut scan = Scan::new();
+                scan.set_name(&rsp.text_on("name", scan_node)?);
+                scan_position.add_scan(scan);
+            }
+
+            for image_node in rsp.nodes_on("images/image", node)? {
+                let mount_calibration_name = rsp.text_on("mountcalibref", image_node)?;
+                let mount_calibration = mount_calibrations.get(&mount_calibration_name)
+                    .ok_or_else(|| Error::XpathNotFound(mount_calibration_name.clone()))?;
+                let camera_calibration =
+                    camera_calibrations.get(mount_calibration.camera_calibration_name())
+                        .ok_or_else(|| {
+                            Error::XpathNotFound(mount_calibration.camera_calibration_name()
+                                .to_string())
+                        })?;
+                let path = rsp.text_on("file", image_node)?;
+                scan_position.add_image(Image::new(path,
+                                                     camera_calibration.clone(),
+                                                     mount_calibration.clone()));
+            }
+
+            scan_positions.insert(scan_position.name().to_string(), scan_position);
+        }
+
+        let path = rsp.path().parent().map(|path| path.to_path_buf()).unwrap_or_else(|| rsp.path().to_path_buf());
+        Ok(Project {
+            camera_calibrations: camera_calibrations,
+            path: path,
+            pop: pop,
+            scan_positions: scan_positions,
+        })
     }
 }
 
@@ -69,7 +249,6 @@ mod tests {
 
     #[test]
     fn project() {
-        use Camera;
         use nalgebra::Matrix4;
 
         let project = Project::from_path("data/project.RiSCAN").unwrap();
@@ -91,8 +270,9 @@ mod tests {
                                                                        1.));
         let actual = project.pop();
         assert_relative_eq!(expected.matrix(), actual.matrix());
-        let camera = Camera::from_path("data/camera.cam").unwrap();
-        assert_eq!(camera, project.camera().unwrap());
+        let camera_calibration = CameraCalibration::new("calib0", 1920, 1080, 1350., 1350., 960.,
+                                                         540., 0., 0., 0., 0., 0.);
+        assert_eq!(camera_calibration, *project.camera_calibration("calib0").unwrap());
     }
 
     #[test]
@@ -105,11 +285,6 @@ mod tests {
         assert!(Project::from_path("data/notaproject.rsp").is_err());
     }
 
-    #[test]
-    fn two_cameras() {
-        assert!(Project::from_path("data/two-cameras.rsp").is_err());
-    }
-
     #[test]
     fn extra_crap_in_doctype() {
         Project::from_path("data/extra-crap-in-doctype.rsp").unwrap();
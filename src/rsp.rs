@@ -0,0 +1,102 @@
+use {Error, Result};
+use sxd_document::{Package, parser};
+use sxd_document::dom::Document;
+use sxd_xpath::{Context, Factory, Value};
+use sxd_xpath::nodeset::Node;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// A parsed `.rsp` project XML document.
+///
+/// This is a thin wrapper around `sxd_document`/`sxd_xpath` that `Project::new` uses to pull
+/// POP, camera calibrations, mount calibrations, and scan positions out of the raw XML.
+pub struct Rsp {
+    package: Package,
+    path: PathBuf,
+}
+
+impl Rsp {
+    /// Reads and parses the `.rsp` file at the given path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Rsp> {
+        let path = ::rsp_path(path)?;
+        let mut xml = String::new();
+        File::open(&path)?.read_to_string(&mut xml)?;
+        let package = parser::parse(&xml)?;
+        Ok(Rsp {
+            package: package,
+            path: path,
+        })
+    }
+
+    /// Returns the path to the underlying `.rsp` file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns this document's root document node.
+    pub fn document(&self) -> Document {
+        self.package.as_document()
+    }
+
+    /// Evaluates an xpath expression relative to the document root.
+    pub fn evaluate(&self, xpath: &str) -> Result<Value> {
+        let document = self.document();
+        self.evaluate_on(xpath, document.root().into())
+    }
+
+    /// Evaluates an xpath expression relative to the given node.
+    pub fn evaluate_on<'d>(&self, xpath: &str, node: Node<'d>) -> Result<Value<'d>> {
+        let factory = Factory::new();
+        let expression = try_opt!(factory.build(xpath)?, Error::XpathNotFound(xpath.to_string()));
+        let context = Context::new();
+        Ok(expression.evaluate(&context, node)?)
+    }
+
+    /// Evaluates an xpath expression and returns its string value.
+    ///
+    /// Returns `Error::XpathNotFound` if the xpath doesn't match anything.
+    pub fn text(&self, xpath: &str) -> Result<String> {
+        match self.evaluate(xpath)? {
+            Value::Nodeset(nodes) => {
+                nodes.document_order()
+                    .into_iter()
+                    .next()
+                    .map(|node| node.string_value())
+                    .ok_or_else(|| Error::XpathNotFound(xpath.to_string()))
+            }
+            value => Ok(value.string()),
+        }
+    }
+
+    /// Evaluates an xpath expression and returns the matching nodes, in document order.
+    pub fn nodes(&self, xpath: &str) -> Result<Vec<Node>> {
+        match self.evaluate(xpath)? {
+            Value::Nodeset(nodes) => Ok(nodes.document_order()),
+            _ => Err(Error::XpathNotFound(xpath.to_string())),
+        }
+    }
+
+    /// Evaluates an xpath expression relative to a node and returns its string value.
+    pub fn text_on<'d>(&self, xpath: &str, node: Node<'d>) -> Result<String> {
+        match self.evaluate_on(xpath, node)? {
+            Value::Nodeset(nodes) => {
+                nodes.document_order()
+                    .into_iter()
+                    .next()
+                    .map(|node| node.string_value())
+                    .ok_or_else(|| Error::XpathNotFound(xpath.to_string()))
+            }
+            value => Ok(value.string()),
+        }
+    }
+
+    /// Evaluates an xpath expression relative to a node and returns the matching nodes, in
+    /// document order.
+    pub fn nodes_on<'d>(&self, xpath: &str, node: Node<'d>) -> Result<Vec<Node<'d>>> {
+        match self.evaluate_on(xpath, node)? {
+            Value::Nodeset(nodes) => Ok(nodes.document_order()),
+            _ => Err(Error::XpathNotFound(xpath.to_string())),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use {Error, Result};
+use std::path::{Path, PathBuf};
+
+/// The on-disk representation of a scan's raw point-cloud data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputCloudFormat {
+    /// Riegl's native rxp stream format.
+    Rxp,
+    /// An ascii export of the point cloud, one point per line.
+    ThreeDD,
+}
+
+impl InputCloudFormat {
+    fn dir_name(&self) -> &'static str {
+        match *self {
+            InputCloudFormat::Rxp => "SINGLESCANS",
+            InputCloudFormat::ThreeDD => "SINGLESCANS_ASCII",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match *self {
+            InputCloudFormat::Rxp => "rxp",
+            InputCloudFormat::ThreeDD => "3dd",
+        }
+    }
+}
+
+/// A single scan, the raw measurement taken from a scan position.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scan {
+    name: String,
+}
+
+impl Scan {
+    /// Creates a new, empty scan.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::Scan;
+    /// let scan = Scan::new();
+    /// ```
+    pub fn new() -> Scan {
+        Scan { name: String::new() }
+    }
+
+    /// Returns this scan's name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::Scan;
+    /// let mut scan = Scan::new();
+    /// scan.set_name("151120_150404");
+    /// assert_eq!("151120_150404", scan.name());
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets this scan's name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::Scan;
+    /// # let mut scan = Scan::new();
+    /// scan.set_name("151120_150404");
+    /// ```
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
+    /// Returns the path to this scan's raw point-cloud data of the given format.
+    ///
+    /// `scan_position_path` is the path to the scan position's directory (e.g.
+    /// `SCANS/SP01`). Returns `Error::MissingScanData` if the expected file isn't present on
+    /// disk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::{InputCloudFormat, Scan};
+    /// let mut scan = Scan::new();
+    /// scan.set_name("151120_150404");
+    /// let path = scan.data_path("data/project.RiSCAN/SCANS/SP01", InputCloudFormat::Rxp);
+    /// ```
+    pub fn data_path<P: AsRef<Path>>(&self,
+                                      scan_position_path: P,
+                                      format: InputCloudFormat)
+                                      -> Result<PathBuf> {
+        let path = scan_position_path.as_ref()
+            .join(format.dir_name())
+            .join(format!("{}.{}", self.name, format.extension()));
+        if path.is_file() {
+            Ok(path)
+        } else {
+            Err(Error::MissingScanData(path))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, File};
+
+    fn scan_position_path(name: &str) -> PathBuf {
+        let path = ::std::env::temp_dir().join(format!("riscan-pro-scan-tests-{}", name));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    #[test]
+    fn data_path_ok() {
+        let scan_position_path = scan_position_path("data-path-ok");
+        let dir = scan_position_path.join(InputCloudFormat::Rxp.dir_name());
+        fs::create_dir_all(&dir).unwrap();
+        let data_path = dir.join("151120_150404.rxp");
+        File::create(&data_path).unwrap();
+
+        let mut scan = Scan::new();
+        scan.set_name("151120_150404");
+        let path = scan.data_path(&scan_position_path, InputCloudFormat::Rxp).unwrap();
+        assert_eq!(data_path, path);
+
+        fs::remove_dir_all(&scan_position_path).unwrap();
+    }
+
+    #[test]
+    fn data_path_missing() {
+        let scan_position_path = scan_position_path("data-path-missing");
+        fs::create_dir_all(&scan_position_path).unwrap();
+
+        let mut scan = Scan::new();
+        scan.set_name("151120_150404");
+        match scan.data_path(&scan_position_path, InputCloudFormat::Rxp) {
+            Err(Error::MissingScanData(_)) => {}
+            other => panic!("expected MissingScanData, got {:?}", other),
+        }
+
+        fs::remove_dir_all(&scan_position_path).unwrap();
+    }
+}
@@ -1,14 +1,23 @@
-use {Error, Matrix, Project, Result, Scan, Vector};
-use nalgebra::Eye;
+use {Error, Image, ImageBuffer, InputCloudFormat, Matrix, Project, Result, Scan, Vector};
+use nalgebra::{Eye, Inverse};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// A scan position.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ScanPosition {
+    images: Vec<Image>,
+    #[cfg_attr(feature = "serde", serde(with = "::serializable_matrix::option_matrix"))]
+    inverse: Option<Matrix>,
     name: String,
+    path: PathBuf,
+    #[cfg_attr(feature = "serde", serde(with = "::serializable_matrix::matrix"))]
     pop: Matrix,
     scans: HashMap<String, Scan>,
+    #[cfg_attr(feature = "serde", serde(with = "::serializable_matrix::matrix"))]
+    socs_to_glcs: Matrix,
+    #[cfg_attr(feature = "serde", serde(with = "::serializable_matrix::matrix"))]
     sop: Matrix,
 }
 
@@ -27,12 +36,16 @@ impl ScanPosition {
         let mut path_buf = fullpath.clone();
         loop {
             if let Ok(project) = Project::from_path(&path_buf) {
-                let scans_path = project.path().unwrap().join("SCANS");
+                let scans_path = project.path().join("SCANS");
                 let subpath = fullpath.strip_prefix(&scans_path)
                     .map_err(|_| Error::NotAScanPosition(path.as_ref().to_path_buf()))?;
                 if let Some(scan_position) = subpath.iter().next() {
                     return project.scan_position(&scan_position.to_string_lossy())
-                        .map(|scan_position| scan_position.clone())
+                        .map(|scan_position| {
+                            let mut scan_position = scan_position.clone();
+                            scan_position.path = scans_path.join(scan_position.name());
+                            scan_position
+                        })
                         .ok_or(Error::NotAScanPosition(path.as_ref().to_path_buf()));
                 } else {
                     return Err(Error::NotAScanPosition(path.as_ref().to_path_buf()));
@@ -54,14 +67,29 @@ impl ScanPosition {
     /// let scan_position = ScanPosition::new();
     /// ```
     pub fn new() -> ScanPosition {
+        let pop = Matrix::new_identity(4);
+        let sop = Matrix::new_identity(4);
+        let socs_to_glcs = pop * sop;
+        let inverse = socs_to_glcs.inverse();
         ScanPosition {
+            images: Vec::new(),
+            inverse: inverse,
             name: String::new(),
-            pop: Matrix::new_identity(4),
+            path: PathBuf::new(),
+            pop: pop,
             scans: HashMap::new(),
-            sop: Matrix::new_identity(4),
+            socs_to_glcs: socs_to_glcs,
+            sop: sop,
         }
     }
 
+    /// Recomputes the cached composed forward (SOCS-to-GLCS) and inverse (GLCS-to-SOCS)
+    /// matrices after the POP or SOP has changed.
+    fn recompute_matrices(&mut self) {
+        self.socs_to_glcs = self.pop * self.sop;
+        self.inverse = self.socs_to_glcs.inverse();
+    }
+
     /// Returns this scan position's name.
     ///
     /// # Examples
@@ -116,6 +144,7 @@ impl ScanPosition {
     /// ```
     pub fn set_sop(&mut self, sop: Matrix) {
         self.sop = sop;
+        self.recompute_matrices();
     }
 
     /// Returns this scan position's POP.
@@ -145,12 +174,16 @@ impl ScanPosition {
     /// ```
     pub fn set_pop(&mut self, pop: Matrix) {
         self.pop = pop;
+        self.recompute_matrices();
     }
 
     /// Converts SOCS coordinates to GLCS coordinates.
     ///
     /// Convert (0., 0., 0.) to get the scanner's origin in GLCS.
     ///
+    /// Uses the cached composed `pop * sop` matrix, so repeated calls don't redo the
+    /// multiplication.
+    ///
     /// # Examples
     ///
     /// ```
@@ -159,10 +192,76 @@ impl ScanPosition {
     /// let (x, y, z) = scan_position.socs_to_glcs((1., 2., 3.));
     /// ```
     pub fn socs_to_glcs(&self, (x, y, z): (f64, f64, f64)) -> (f64, f64, f64) {
-        let glcs = self.pop * self.sop * Vector::new(x, y, z, 1.);
+        let glcs = self.socs_to_glcs * Vector::new(x, y, z, 1.);
         (glcs.x, glcs.y, glcs.z)
     }
 
+    /// Converts GLCS coordinates to SOCS coordinates, the inverse of `socs_to_glcs`.
+    ///
+    /// Returns `Error::MatrixNotInvertible` if the composed `pop * sop` matrix can't be
+    /// inverted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::ScanPosition;
+    /// # let scan_position = ScanPosition::new();
+    /// let (x, y, z) = scan_position.glcs_to_socs((1., 2., 3.)).unwrap();
+    /// ```
+    pub fn glcs_to_socs(&self, (x, y, z): (f64, f64, f64)) -> Result<(f64, f64, f64)> {
+        let inverse = self.inverse.ok_or(Error::MatrixNotInvertible)?;
+        let socs = inverse * Vector::new(x, y, z, 1.);
+        Ok((socs.x, socs.y, socs.z))
+    }
+
+    /// Converts many SOCS points to GLCS points in one pass.
+    ///
+    /// This avoids reconstructing the composed matrix per-point, which matters when
+    /// transforming real scans with millions of points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::ScanPosition;
+    /// # let scan_position = ScanPosition::new();
+    /// let points = vec![(1., 2., 3.), (4., 5., 6.)];
+    /// let glcs: Vec<_> = scan_position.socs_to_glcs_iter(points).collect();
+    /// ```
+    pub fn socs_to_glcs_iter<'a, I>(&'a self, points: I) -> Box<Iterator<Item = (f64, f64, f64)> + 'a>
+        where I: IntoIterator<Item = (f64, f64, f64)> + 'a
+    {
+        Box::new(points.into_iter().map(move |(x, y, z)| {
+            let glcs = self.socs_to_glcs * Vector::new(x, y, z, 1.);
+            (glcs.x, glcs.y, glcs.z)
+        }))
+    }
+
+    /// Converts many GLCS points to SOCS points in one pass, the inverse of
+    /// `socs_to_glcs_iter`.
+    ///
+    /// Returns `Error::MatrixNotInvertible` if the composed `pop * sop` matrix can't be
+    /// inverted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::ScanPosition;
+    /// # let scan_position = ScanPosition::new();
+    /// let points = vec![(1., 2., 3.), (4., 5., 6.)];
+    /// let socs: Vec<_> = scan_position.glcs_to_socs_iter(points).unwrap().collect();
+    /// ```
+    pub fn glcs_to_socs_iter<'a, I>(&'a self,
+                                     points: I)
+                                     -> Result<Box<Iterator<Item = (f64, f64, f64)> + 'a>>
+        where I: IntoIterator<Item = (f64, f64, f64)> + 'a
+    {
+        let inverse = self.inverse.ok_or(Error::MatrixNotInvertible)?;
+        Ok(Box::new(points.into_iter().map(move |(x, y, z)| {
+            let socs = inverse * Vector::new(x, y, z, 1.);
+            (socs.x, socs.y, socs.z)
+        })))
+    }
+
     /// Returns a reference to the scan with the given name.
     ///
     /// # Examples
@@ -188,11 +287,113 @@ impl ScanPosition {
     pub fn add_scan(&mut self, scan: Scan) {
         self.scans.insert(scan.name().to_string(), scan);
     }
+
+    /// Returns every scan in this scan position together with the path to its data file in the
+    /// given input cloud format.
+    ///
+    /// Returns an error if any scan is missing its data file for that format, rather than
+    /// silently skipping it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::{InputCloudFormat, ScanPosition};
+    /// let scan_position = ScanPosition::from_path("data/project.RiSCAN/SCANS/SP01").unwrap();
+    /// let scans = scan_position.scans_with_data(InputCloudFormat::Rxp).unwrap();
+    /// ```
+    pub fn scans_with_data(&self, format: InputCloudFormat) -> Result<Vec<(&Scan, PathBuf)>> {
+        self.scans
+            .values()
+            .map(|scan| scan.data_path(&self.path, format).map(|path| (scan, path)))
+            .collect()
+    }
+
+    /// Returns this scan position's images.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::ScanPosition;
+    /// # let scan_position = ScanPosition::new();
+    /// let images = scan_position.images();
+    /// ```
+    pub fn images(&self) -> &[Image] {
+        &self.images
+    }
+
+    /// Adds an image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::{CameraCalibration, Image, Matrix, MountCalibration, ScanPosition};
+    /// # use nalgebra::Eye;
+    /// let mut scan_position = ScanPosition::new();
+    /// let camera_calibration = CameraCalibration::new("calib0", 1920, 1080, 1350., 1350., 960.,
+    ///                                                  540., 0., 0., 0., 0., 0.);
+    /// let mount_calibration = MountCalibration::new("mount0", "calib0", Matrix::new_identity(4));
+    /// scan_position.add_image(Image::new("IMAGES/image001.jpg", camera_calibration, mount_calibration));
+    /// ```
+    pub fn add_image(&mut self, image: Image) {
+        self.images.push(image);
+    }
+
+    /// Projects a SOCS point into the given image, returning a sub-pixel `(u, v)` coordinate.
+    ///
+    /// Runs the full Riegl chain: the point is first carried from SOCS into the image's camera
+    /// coordinate system (CAMCS) via the image's mount calibration, then projected through the
+    /// camera calibration's intrinsics. Returns `None` if the point is behind the camera or
+    /// falls outside of the image's bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::{CameraCalibration, Image, Matrix, MountCalibration, ScanPosition};
+    /// # use nalgebra::Eye;
+    /// let scan_position = ScanPosition::new();
+    /// let camera_calibration = CameraCalibration::new("calib0", 1920, 1080, 1350., 1350., 960.,
+    ///                                                  540., 0., 0., 0., 0., 0.);
+    /// let mount_calibration = MountCalibration::new("mount0", "calib0", Matrix::new_identity(4));
+    /// let image = Image::new("IMAGES/image001.jpg", camera_calibration, mount_calibration);
+    /// // Outside of the image's height, since v=1440 >= 1080.
+    /// assert_eq!(None, scan_position.project_into_image((1., 2., 3.), &image));
+    /// ```
+    pub fn project_into_image(&self, (x, y, z): (f64, f64, f64), image: &Image) -> Option<(f64, f64)> {
+        let camcs = image.mount_calibration().matrix() * Vector::new(x, y, z, 1.);
+        image.camera_calibration().project((camcs.x, camcs.y, camcs.z))
+    }
+
+    /// Returns the nearest-neighbor RGB color of a SOCS point as seen by the given image.
+    ///
+    /// Returns `None` if the point doesn't project into the image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use riscan_pro::{CameraCalibration, Image, ImageBuffer, Matrix, MountCalibration, ScanPosition};
+    /// # use nalgebra::Eye;
+    /// let scan_position = ScanPosition::new();
+    /// let camera_calibration = CameraCalibration::new("calib0", 1920, 1080, 1350., 1350., 960.,
+    ///                                                  540., 0., 0., 0., 0., 0.);
+    /// let mount_calibration = MountCalibration::new("mount0", "calib0", Matrix::new_identity(4));
+    /// let image = Image::new("IMAGES/image001.jpg", camera_calibration, mount_calibration);
+    /// let buffer = ImageBuffer::new(1, 1, vec![255, 0, 0]);
+    /// // The point doesn't project into the image, so there's no color to sample.
+    /// assert_eq!(None, scan_position.color_of((1., 2., 3.), &image, &buffer));
+    /// ```
+    pub fn color_of(&self,
+                     socs: (f64, f64, f64),
+                     image: &Image,
+                     buffer: &ImageBuffer)
+                     -> Option<(u8, u8, u8)> {
+        self.project_into_image(socs, image).and_then(|(u, v)| buffer.nearest(u, v))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use Project;
+    use super::*;
+    use {CameraCalibration, MountCalibration, Project};
 
     #[test]
     fn scan_position_glcs() {
@@ -203,4 +404,74 @@ mod tests {
         assert!((-5519674.02 - y).abs() < 1e-2);
         assert!((3143445.58 - z).abs() < 1e-2);
     }
+
+    #[test]
+    fn glcs_to_socs_is_the_inverse_of_socs_to_glcs() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_position("SP01").unwrap();
+        let socs = (1., 2., 3.);
+        let glcs = scan_position.socs_to_glcs(socs);
+        let roundtrip = scan_position.glcs_to_socs(glcs).unwrap();
+        assert!((socs.0 - roundtrip.0).abs() < 1e-6);
+        assert!((socs.1 - roundtrip.1).abs() < 1e-6);
+        assert!((socs.2 - roundtrip.2).abs() < 1e-6);
+    }
+
+    fn singular_scan_position() -> ScanPosition {
+        #[cfg_attr(rustfmt, rustfmt_skip)]
+        let singular = Matrix::new(0., 0., 0., 0.,
+                                    0., 0., 0., 0.,
+                                    0., 0., 0., 0.,
+                                    0., 0., 0., 0.);
+        let mut scan_position = ScanPosition::new();
+        scan_position.set_sop(singular);
+        scan_position
+    }
+
+    #[test]
+    fn glcs_to_socs_is_an_error_when_not_invertible() {
+        let scan_position = singular_scan_position();
+        match scan_position.glcs_to_socs((1., 2., 3.)) {
+            Err(Error::MatrixNotInvertible) => {}
+            other => panic!("expected MatrixNotInvertible, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn glcs_to_socs_iter_is_an_error_when_not_invertible() {
+        let scan_position = singular_scan_position();
+        match scan_position.glcs_to_socs_iter(vec![(1., 2., 3.)]) {
+            Err(Error::MatrixNotInvertible) => {}
+            other => panic!("expected MatrixNotInvertible, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn project_into_image_and_color_of() {
+        let scan_position = ScanPosition::new();
+        let camera_calibration = CameraCalibration::new("calib0", 100, 100, 50., 50., 50., 50., 0.,
+                                                          0., 0., 0., 0.);
+        let mount_calibration = MountCalibration::new("mount0", "calib0", Matrix::new_identity(4));
+        let image = Image::new("IMAGES/image001.jpg", camera_calibration, mount_calibration);
+        assert_eq!(Some((50., 50.)), scan_position.project_into_image((0., 0., 1.), &image));
+
+        let mut data = vec![0; 51 * 51 * 3];
+        let index = (50 * 51 + 50) * 3;
+        data[index] = 255;
+        data[index + 1] = 127;
+        data[index + 2] = 63;
+        let buffer = ImageBuffer::new(51, 51, data);
+        assert_eq!(Some((255, 127, 63)), scan_position.color_of((0., 0., 1.), &image, &buffer));
+    }
+
+    #[test]
+    fn socs_to_glcs_iter_matches_socs_to_glcs() {
+        let project = Project::from_path("data/project.RiSCAN").unwrap();
+        let scan_position = project.scan_position("SP01").unwrap();
+        let points = vec![(1., 2., 3.), (4., 5., 6.)];
+        let expected: Vec<_> =
+            points.iter().cloned().map(|point| scan_position.socs_to_glcs(point)).collect();
+        let actual: Vec<_> = scan_position.socs_to_glcs_iter(points).collect();
+        assert_eq!(expected, actual);
+    }
 }
@@ -0,0 +1,98 @@
+//! Serde support for the `nalgebra` matrix types used throughout this crate.
+//!
+//! `Matrix` and `Projective3` are type aliases for `nalgebra` types, which don't implement
+//! `Serialize`/`Deserialize` in the version this crate depends on -- and can't have one derived
+//! for them here, since they're foreign types. `SerializableMatrix` gives them an explicit,
+//! stable JSON representation (a flat, row-major array of sixteen floats), and the `matrix`,
+//! `option_matrix`, and `projective3` submodules wire that representation up to struct fields
+//! via `#[serde(with = "...")]`.
+
+use Matrix;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+struct SerializableMatrix([f64; 16]);
+
+impl From<Matrix> for SerializableMatrix {
+    fn from(matrix: Matrix) -> SerializableMatrix {
+        #[cfg_attr(rustfmt, rustfmt_skip)]
+        SerializableMatrix([
+            matrix.m11, matrix.m12, matrix.m13, matrix.m14,
+            matrix.m21, matrix.m22, matrix.m23, matrix.m24,
+            matrix.m31, matrix.m32, matrix.m33, matrix.m34,
+            matrix.m41, matrix.m42, matrix.m43, matrix.m44,
+        ])
+    }
+}
+
+impl From<SerializableMatrix> for Matrix {
+    fn from(serializable: SerializableMatrix) -> Matrix {
+        let v = serializable.0;
+        #[cfg_attr(rustfmt, rustfmt_skip)]
+        let matrix = Matrix::new(v[0], v[1], v[2], v[3],
+                                  v[4], v[5], v[6], v[7],
+                                  v[8], v[9], v[10], v[11],
+                                  v[12], v[13], v[14], v[15]);
+        matrix
+    }
+}
+
+/// Serializes/deserializes a `Matrix` field as a flat, row-major array of sixteen floats.
+pub mod matrix {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use Matrix;
+    use super::SerializableMatrix;
+
+    #[allow(missing_docs)]
+    pub fn serialize<S: Serializer>(matrix: &Matrix, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializableMatrix::from(*matrix).0.serialize(serializer)
+    }
+
+    #[allow(missing_docs)]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Matrix, D::Error> {
+        let values = <[f64; 16]>::deserialize(deserializer)?;
+        Ok(Matrix::from(SerializableMatrix(values)))
+    }
+}
+
+/// Serializes/deserializes an `Option<Matrix>` field the same way as `matrix`, preserving `None`.
+pub mod option_matrix {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use Matrix;
+    use super::SerializableMatrix;
+
+    #[allow(missing_docs)]
+    pub fn serialize<S: Serializer>(matrix: &Option<Matrix>,
+                                     serializer: S)
+                                     -> Result<S::Ok, S::Error> {
+        matrix.map(SerializableMatrix::from).map(|m| m.0).serialize(serializer)
+    }
+
+    #[allow(missing_docs)]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D)
+                                                    -> Result<Option<Matrix>, D::Error> {
+        let values = Option::<[f64; 16]>::deserialize(deserializer)?;
+        Ok(values.map(SerializableMatrix).map(Matrix::from))
+    }
+}
+
+/// Serializes/deserializes a `Projective3` field the same way as `matrix`.
+pub mod projective3 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use {Matrix, Projective3};
+    use super::SerializableMatrix;
+
+    #[allow(missing_docs)]
+    pub fn serialize<S: Serializer>(projective: &Projective3,
+                                     serializer: S)
+                                     -> Result<S::Ok, S::Error> {
+        SerializableMatrix::from(*projective.matrix()).0.serialize(serializer)
+    }
+
+    #[allow(missing_docs)]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D)
+                                                    -> Result<Projective3, D::Error> {
+        let values = <[f64; 16]>::deserialize(deserializer)?;
+        let matrix = Matrix::from(SerializableMatrix(values));
+        Ok(Projective3::from_matrix_unchecked(matrix))
+    }
+}
@@ -0,0 +1,75 @@
+use {Error, Matrix, Result};
+
+/// Parses a RiSCAN Pro matrix string into a `Matrix`.
+///
+/// RiSCAN stores POP, SOP, and mount calibration matrices as a single string of sixteen
+/// whitespace-separated floats in row-major order. This tolerates arbitrary runs of whitespace
+/// (including newlines) between values, and errors cleanly if there are too few or too many
+/// tokens, or if a token isn't a valid float.
+///
+/// # Examples
+///
+/// ```
+/// # // parse_matrix4 isn't public, so this can't be doctested directly.
+/// ```
+pub fn parse_matrix4(s: &str) -> Result<Matrix> {
+    let mut values = [0.; 16];
+    let mut n = 0;
+    for token in s.split_whitespace() {
+        if n >= values.len() {
+            return Err(Error::MatrixParse(s.to_string()));
+        }
+        values[n] = token.parse().map_err(|_| Error::MatrixParse(s.to_string()))?;
+        n += 1;
+    }
+    if n != values.len() {
+        return Err(Error::MatrixParse(s.to_string()));
+    }
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    Ok(Matrix::new(values[0], values[1], values[2], values[3],
+                   values[4], values[5], values[6], values[7],
+                   values[8], values[9], values[10], values[11],
+                   values[12], values[13], values[14], values[15]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::Eye;
+
+    #[test]
+    fn parse_matrix4_identity() {
+        let matrix = parse_matrix4("1 0 0 0\n0 1 0 0\n0 0 1 0\n0 0 0 1").unwrap();
+        assert_eq!(Matrix::new_identity(4), matrix);
+    }
+
+    #[test]
+    fn parse_matrix4_extra_whitespace() {
+        let matrix = parse_matrix4("  1 0 0 0   0 1 0 0\n\n0 0 1 0  0 0 0 1  ").unwrap();
+        assert_eq!(Matrix::new_identity(4), matrix);
+    }
+
+    #[test]
+    fn parse_matrix4_too_few_tokens() {
+        match parse_matrix4("1 2 3") {
+            Err(Error::MatrixParse(_)) => {}
+            other => panic!("expected MatrixParse, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_matrix4_too_many_tokens() {
+        match parse_matrix4(&"1 ".repeat(17)) {
+            Err(Error::MatrixParse(_)) => {}
+            other => panic!("expected MatrixParse, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_matrix4_malformed_float() {
+        match parse_matrix4(&"notafloat ".repeat(16)) {
+            Err(Error::MatrixParse(_)) => {}
+            other => panic!("expected MatrixParse, got {:?}", other),
+        }
+    }
+}